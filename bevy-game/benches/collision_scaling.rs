@@ -0,0 +1,95 @@
+// Benchmarks comparing the uniform-grid broadphase against the naive all-pairs
+// reference across 1k/10k/100k sprites. The grid path should scale roughly
+// linearly while the naive path scales quadratically; the `scaling_is_sub_quadratic`
+// guard fails the bench if that relationship ever regresses.
+
+use std::time::Instant;
+
+use bevy_game::{resolve_collisions_grid, resolve_collisions_naive, Sprite};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+/// Deterministic sprite field built from a tiny LCG so benchmark inputs are
+/// reproducible without pulling in an RNG crate.
+fn make_sprites(n: usize, extent: f64) -> Vec<Sprite> {
+    let mut state = 0x2545_F491_4F6C_DD1Du64;
+    let mut next = || {
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (state >> 11) as f64 / (1u64 << 53) as f64
+    };
+    (0..n)
+        .map(|_| {
+            let x = next() * extent;
+            let y = next() * extent;
+            let vx = (next() - 0.5) * 200.0;
+            let vy = (next() - 0.5) * 200.0;
+            Sprite::new(x, y, vx, vy, 20.0, "#888888".to_string())
+        })
+        .collect()
+}
+
+fn bench_collisions(c: &mut Criterion) {
+    let mut group = c.benchmark_group("collision_broadphase");
+    // Keep the density constant as the count grows so the grid stays effective.
+    for &n in &[1_000usize, 10_000, 100_000] {
+        let extent = (n as f64).sqrt() * 40.0;
+        let sprites = make_sprites(n, extent);
+
+        group.bench_with_input(BenchmarkId::new("grid", n), &sprites, |b, sprites| {
+            b.iter(|| {
+                let mut scratch = sprites.clone();
+                resolve_collisions_grid(black_box(&mut scratch));
+            });
+        });
+
+        // The naive path is quadratic, so only exercise the smaller counts.
+        if n <= 10_000 {
+            group.bench_with_input(BenchmarkId::new("naive", n), &sprites, |b, sprites| {
+                b.iter(|| {
+                    let mut scratch = sprites.clone();
+                    resolve_collisions_naive(black_box(&mut scratch));
+                });
+            });
+        }
+    }
+    group.finish();
+
+    scaling_is_sub_quadratic();
+}
+
+/// Measure the grid path at 1k and 10k sprites and assert the 10x size increase
+/// costs well under the 100x a quadratic algorithm would incur. Each size is
+/// warmed up and then timed across several runs, taking the median so a single
+/// cold-cache or load-spike sample can't fail the guard spuriously.
+fn scaling_is_sub_quadratic() {
+    const RUNS: usize = 7;
+
+    let time_grid = |n: usize| {
+        let extent = (n as f64).sqrt() * 40.0;
+        let sprites = make_sprites(n, extent);
+
+        let run_once = || {
+            let mut scratch = sprites.clone();
+            let start = Instant::now();
+            resolve_collisions_grid(&mut scratch);
+            start.elapsed().as_secs_f64()
+        };
+
+        // Discard a warm-up run so allocation/cache effects don't skew the first
+        // measured sample, then take the median of several timings.
+        run_once();
+        let mut samples: Vec<f64> = (0..RUNS).map(|_| run_once()).collect();
+        samples.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        samples[RUNS / 2]
+    };
+
+    let small = time_grid(1_000).max(1e-9);
+    let large = time_grid(10_000);
+    let ratio = large / small;
+    assert!(
+        ratio < 50.0,
+        "grid collision scaled {ratio:.1}x for a 10x size increase (expected sub-quadratic)"
+    );
+}
+
+criterion_group!(benches, bench_collisions);
+criterion_main!(benches);
@@ -13,7 +13,89 @@ use std::rc::Rc;
 // 4. Implement proper ECS architecture
 // 5. Add Bevy's input handling and rendering systems
 
-#[derive(Clone)]
+// Web Audio sound-effect manager. Decoded clips live in a shared map so the
+// async preloads can populate it after construction; `play` fires a fresh
+// one-shot source node each call, allowing overlapping playback.
+struct AudioManager {
+    context: web_sys::AudioContext,
+    buffers: Rc<RefCell<std::collections::HashMap<String, web_sys::AudioBuffer>>>,
+    resumed: bool,
+}
+
+impl AudioManager {
+    fn new() -> Result<Self, JsValue> {
+        Ok(AudioManager {
+            context: web_sys::AudioContext::new()?,
+            buffers: Rc::new(RefCell::new(std::collections::HashMap::new())),
+            resumed: false,
+        })
+    }
+
+    // Fetch `url`, decode it, and store the buffer under `name`. Runs as a
+    // chain of promise callbacks so construction doesn't block on the network.
+    fn preload(&self, name: &str, url: &str) {
+        let window = match web_sys::window() {
+            Some(window) => window,
+            None => return,
+        };
+
+        let context = self.context.clone();
+        let buffers = self.buffers.clone();
+        let name = name.to_string();
+
+        let to_array_buffer = Closure::wrap(Box::new(move |resp: JsValue| -> JsValue {
+            let resp: web_sys::Response = resp.unchecked_into();
+            resp.array_buffer().map(JsValue::from).unwrap_or(JsValue::UNDEFINED)
+        }) as Box<dyn FnMut(JsValue) -> JsValue>);
+
+        let to_decoded = Closure::wrap(Box::new(move |array_buffer: JsValue| -> JsValue {
+            let array_buffer: js_sys::ArrayBuffer = array_buffer.unchecked_into();
+            context
+                .decode_audio_data(&array_buffer)
+                .map(JsValue::from)
+                .unwrap_or(JsValue::UNDEFINED)
+        }) as Box<dyn FnMut(JsValue) -> JsValue>);
+
+        let store = Closure::wrap(Box::new(move |buffer: JsValue| -> JsValue {
+            let buffer: web_sys::AudioBuffer = buffer.unchecked_into();
+            buffers.borrow_mut().insert(name.clone(), buffer);
+            JsValue::UNDEFINED
+        }) as Box<dyn FnMut(JsValue) -> JsValue>);
+
+        let _ = window
+            .fetch_with_str(url)
+            .then(&to_array_buffer)
+            .then(&to_decoded)
+            .then(&store);
+
+        // The promise chain outlives this call, so leak the closures.
+        to_array_buffer.forget();
+        to_decoded.forget();
+        store.forget();
+    }
+
+    // Play a preloaded clip by name; silently ignores unknown names and any
+    // Web Audio errors so missing assets never break the game loop.
+    fn play(&self, name: &str) {
+        if let Some(buffer) = self.buffers.borrow().get(name) {
+            if let Ok(source) = self.context.create_buffer_source() {
+                source.set_buffer(Some(buffer));
+                let _ = source.connect_with_audio_node(&self.context.destination());
+                let _ = source.start();
+            }
+        }
+    }
+
+    // Browsers suspend audio until a user gesture; resume once on first click.
+    fn resume(&mut self) {
+        if !self.resumed {
+            let _ = self.context.resume();
+            self.resumed = true;
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
 struct Ball {
     x: f64,
     y: f64,
@@ -33,22 +115,28 @@ impl Ball {
         }
     }
 
-    // Update ball position - will become a Bevy System
-    fn update(&mut self, delta_time: f64, canvas_width: f64, canvas_height: f64) {
+    // Update ball position - will become a Bevy System. Returns whether the
+    // ball bounced off a wall this step so the caller can play a sound effect.
+    fn update(&mut self, delta_time: f64, canvas_width: f64, canvas_height: f64) -> bool {
         self.x += self.velocity_x * delta_time;
         self.y += self.velocity_y * delta_time;
 
         // Bounce off walls
+        let mut bounced = false;
         if self.x + self.radius > canvas_width || self.x - self.radius < 0.0 {
             self.velocity_x = -self.velocity_x;
+            bounced = true;
         }
         if self.y + self.radius > canvas_height || self.y - self.radius < 0.0 {
             self.velocity_y = -self.velocity_y;
+            bounced = true;
         }
 
         // Keep within bounds
         self.x = self.x.clamp(self.radius, canvas_width - self.radius);
         self.y = self.y.clamp(self.radius, canvas_height - self.radius);
+
+        bounced
     }
 
     // Render ball - will become a Bevy Sprite Component
@@ -73,12 +161,300 @@ impl Ball {
     }
 }
 
+// Brick layout constants - classic canvas breakout grid
+const BRICK_ROW_COUNT: usize = 5;
+const BRICK_COLUMN_COUNT: usize = 8;
+const BRICK_WIDTH: f64 = 70.0;
+const BRICK_HEIGHT: f64 = 20.0;
+const BRICK_PADDING: f64 = 10.0;
+const BRICK_OFFSET_TOP: f64 = 40.0;
+const BRICK_OFFSET_LEFT: f64 = 30.0;
+
+// A single destructible brick - will become a Bevy Component
+#[derive(Clone, PartialEq)]
+struct Brick {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+    status: u8, // 1 = intact, 0 = cleared
+}
+
+// Brick grid resource laid out in rows and columns
+#[derive(Clone, PartialEq)]
+struct Bricks {
+    grid: Vec<Vec<Brick>>,
+}
+
+impl Bricks {
+    fn new() -> Self {
+        let mut grid = Vec::with_capacity(BRICK_ROW_COUNT);
+        for row in 0..BRICK_ROW_COUNT {
+            let mut columns = Vec::with_capacity(BRICK_COLUMN_COUNT);
+            for col in 0..BRICK_COLUMN_COUNT {
+                let x = BRICK_OFFSET_LEFT + col as f64 * (BRICK_WIDTH + BRICK_PADDING);
+                let y = BRICK_OFFSET_TOP + row as f64 * (BRICK_HEIGHT + BRICK_PADDING);
+                columns.push(Brick {
+                    x,
+                    y,
+                    width: BRICK_WIDTH,
+                    height: BRICK_HEIGHT,
+                    status: 1,
+                });
+            }
+            grid.push(columns);
+        }
+        Bricks { grid }
+    }
+
+    // Number of bricks still intact - used for the win condition
+    fn remaining(&self) -> usize {
+        self.grid
+            .iter()
+            .flatten()
+            .filter(|brick| brick.status == 1)
+            .count()
+    }
+
+    // Render bricks - will become a Bevy Sprite Component
+    fn render(&self, context: &CanvasRenderingContext2d) {
+        context.set_fill_style(&JsValue::from_str("#0095dd"));
+        for brick in self.grid.iter().flatten() {
+            if brick.status == 1 {
+                context.fill_rect(brick.x, brick.y, brick.width, brick.height);
+            }
+        }
+    }
+}
+
+// An axis-aligned sprite with a square bounding box centered on `(x, y)`.
+#[derive(Clone, Copy)]
+struct Sprite {
+    x: f64,
+    y: f64,
+    velocity_x: f64,
+    velocity_y: f64,
+    size: f64,
+}
+
+impl Sprite {
+    fn new(x: f64, y: f64, velocity_x: f64, velocity_y: f64, size: f64) -> Self {
+        Sprite { x, y, velocity_x, velocity_y, size }
+    }
+
+    fn update(&mut self, delta_time: f64, width: f64, height: f64) {
+        self.x += self.velocity_x * delta_time;
+        self.y += self.velocity_y * delta_time;
+
+        let half = self.size / 2.0;
+        if self.x - half < 0.0 || self.x + half > width {
+            self.velocity_x = -self.velocity_x;
+        }
+        if self.y - half < 0.0 || self.y + half > height {
+            self.velocity_y = -self.velocity_y;
+        }
+    }
+
+    // True when this sprite's AABB overlaps `other`'s.
+    fn overlaps(&self, other: &Sprite) -> bool {
+        let half = self.size / 2.0 + other.size / 2.0;
+        (self.x - other.x).abs() < half && (self.y - other.y).abs() < half
+    }
+}
+
+// A collision between two sprites, identified by their indices. `just_began`
+// is true on the frame the overlap starts and false on the frame it ends, so
+// game logic can treat enter and exit distinctly.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct CollisionEvent {
+    a: usize,
+    b: usize,
+    just_began: bool,
+}
+
+// A set of sprites plus the collision state needed to emit enter/exit events.
+struct SpriteSet {
+    sprites: Vec<Sprite>,
+    colliding: std::collections::HashSet<(usize, usize)>,
+}
+
+impl SpriteSet {
+    fn new() -> Self {
+        SpriteSet {
+            sprites: Vec::new(),
+            colliding: std::collections::HashSet::new(),
+        }
+    }
+
+    // Seed a small demo set: two sprites closing on each other across the
+    // playfield so the collision-event system actually fires enter/exit events
+    // (and the collision sound) at runtime, not just in tests.
+    fn demo(width: f64, height: f64) -> Self {
+        let mut set = SpriteSet::new();
+        let y = height / 2.0;
+        set.sprites.push(Sprite::new(width * 0.25, y, 90.0, 0.0, 30.0));
+        set.sprites.push(Sprite::new(width * 0.75, y, -90.0, 0.0, 30.0));
+        set
+    }
+
+    fn update(&mut self, delta_time: f64, width: f64, height: f64) {
+        for sprite in &mut self.sprites {
+            sprite.update(delta_time, width, height);
+        }
+    }
+
+    // Check every sprite pair for AABB overlap and emit one event per pair that
+    // started or stopped colliding since the previous call.
+    fn detect_collisions(&mut self) -> Vec<CollisionEvent> {
+        let mut events = Vec::new();
+        let mut now = std::collections::HashSet::new();
+
+        let n = self.sprites.len();
+        for a in 0..n {
+            for b in (a + 1)..n {
+                if self.sprites[a].overlaps(&self.sprites[b]) {
+                    now.insert((a, b));
+                    if !self.colliding.contains(&(a, b)) {
+                        events.push(CollisionEvent { a, b, just_began: true });
+                    }
+                }
+            }
+        }
+
+        for &(a, b) in &self.colliding {
+            if !now.contains(&(a, b)) {
+                events.push(CollisionEvent { a, b, just_began: false });
+            }
+        }
+
+        self.colliding = now;
+        events
+    }
+}
+
+// Fixed simulation step - a constant dt keeps the simulation deterministic and
+// replayable regardless of the real frame rate.
+const TIMESTEP: f64 = 1.0 / 60.0;
+
+// Number of recent frames kept for rollback. ~2s at 60fps is plenty to absorb
+// the input lag a peer-to-peer session has to reconcile.
+const HISTORY_LEN: usize = 120;
+
+// Velocity nudge applied to the ball per fixed step while an arrow key is held.
+const KEY_ACCEL: f64 = 60.0;
+
+// Tracks keyboard state across frames: which keys are currently held, plus a
+// queue of fresh presses. Buffering presses into a queue and consuming one per
+// fixed step means rapid taps between steps aren't dropped the way polling
+// only at step time would drop them.
+struct KeyboardState {
+    held: std::collections::HashSet<String>,
+    just_pressed: std::collections::VecDeque<String>,
+}
+
+impl KeyboardState {
+    fn new() -> Self {
+        KeyboardState {
+            held: std::collections::HashSet::new(),
+            just_pressed: std::collections::VecDeque::new(),
+        }
+    }
+
+    // Record a keydown. `keydown` repeats while a key is held, so only the
+    // first transition to "held" enqueues a fresh press.
+    fn press(&mut self, key: String) {
+        if self.held.insert(key.clone()) {
+            self.just_pressed.push_back(key);
+        }
+    }
+
+    fn release(&mut self, key: &str) {
+        self.held.remove(key);
+    }
+
+    fn pressed(&self, key: &str) -> bool {
+        self.held.contains(key)
+    }
+
+    // Pop the oldest buffered press, if any.
+    fn take_press(&mut self) -> Option<String> {
+        self.just_pressed.pop_front()
+    }
+}
+
+// Map an arrow key to a velocity nudge; other keys contribute nothing.
+fn arrow_to_accel(key: &str) -> Option<(f64, f64)> {
+    match key {
+        "ArrowUp" => Some((0.0, -KEY_ACCEL)),
+        "ArrowDown" => Some((0.0, KEY_ACCEL)),
+        "ArrowLeft" => Some((-KEY_ACCEL, 0.0)),
+        "ArrowRight" => Some((KEY_ACCEL, 0.0)),
+        _ => None,
+    }
+}
+
+// Per-frame input applied by `step`. Defaulting to "nothing happened" lets a
+// frame advance with no player action, which rollback relies on.
+#[derive(Clone, Default)]
+struct Input {
+    // Redirect target from a click this frame, if any.
+    redirect: Option<(f64, f64)>,
+    // Velocity nudge from an arrow key this frame, if any.
+    accelerate: Option<(f64, f64)>,
+}
+
+// Side-effect flags produced by a simulation step, consumed by the audio layer
+// outside the deterministic core.
+#[derive(Clone, Copy, Default)]
+struct StepFx {
+    wall_bounce: bool,
+    brick_hit: bool,
+}
+
+// The full simulation state for one frame. Everything the deterministic `step`
+// reads or writes lives here so a frame can be snapshotted and restored exactly;
+// nothing here touches the DOM. Snapshots are plain clones: the struct has no
+// byte-level serialization.
+#[derive(Clone, PartialEq)]
+struct SimState {
+    ball: Ball,
+    bricks: Bricks,
+    score: u32,
+    won: bool,
+    width: f64,
+    height: f64,
+}
+
+impl SimState {
+    fn new(width: f64, height: f64) -> Self {
+        SimState {
+            ball: Ball::new(width / 2.0, height / 2.0),
+            bricks: Bricks::new(),
+            score: 0,
+            won: false,
+            width,
+            height,
+        }
+    }
+}
+
 // Game state - will become Bevy Resources and World
 struct GameState {
-    ball: Ball,
+    sim: SimState,
     canvas: HtmlCanvasElement,
     context: CanvasRenderingContext2d,
     last_frame_time: f64,
+    accumulator: f64,
+    frame: u64,
+    pending_input: Input,
+    // Ring buffer of recent (frame, pre-step snapshot, input) for rollback.
+    history: std::collections::VecDeque<(u64, SimState, Input)>,
+    // Generic AABB sprites driven by the collision-event system.
+    sprites: SpriteSet,
+    // Shared with the window keydown/keyup listeners registered in Game::start.
+    keyboard: Rc<RefCell<KeyboardState>>,
+    // Sound effects; None if the browser has no Web Audio support.
+    audio: Option<AudioManager>,
 }
 
 impl GameState {
@@ -95,16 +471,149 @@ impl GameState {
             .unwrap()
             .dyn_into::<CanvasRenderingContext2d>()?;
 
-        let ball = Ball::new(canvas.width() as f64 / 2.0, canvas.height() as f64 / 2.0);
+        let sim = SimState::new(canvas.width() as f64, canvas.height() as f64);
+        let sprites = SpriteSet::demo(canvas.width() as f64, canvas.height() as f64);
+
+        // Set up audio and kick off the clip preloads; failure just mutes the game.
+        let audio = AudioManager::new().ok();
+        if let Some(audio) = &audio {
+            audio.preload("bounce", "sounds/bounce.wav");
+            audio.preload("brick", "sounds/brick.wav");
+            audio.preload("collision", "sounds/collision.wav");
+        }
 
         Ok(GameState {
-            ball,
+            sim,
             canvas,
             context,
             last_frame_time: 0.0,
+            accumulator: 0.0,
+            frame: 0,
+            pending_input: Input::default(),
+            history: std::collections::VecDeque::with_capacity(HISTORY_LEN),
+            sprites,
+            keyboard: Rc::new(RefCell::new(KeyboardState::new())),
+            audio,
         })
     }
 
+    // Advance the generic sprite set and react to collision events, bouncing
+    // both sprites apart when a new overlap begins. Returns the frame's events
+    // so callers can drive their own game logic.
+    fn update_sprites(&mut self) -> Vec<CollisionEvent> {
+        self.sprites
+            .update(TIMESTEP, self.canvas.width() as f64, self.canvas.height() as f64);
+        let events = self.sprites.detect_collisions();
+        for event in &events {
+            if event.just_began {
+                let (a, b) = (event.a, event.b);
+                self.sprites.sprites[a].velocity_x = -self.sprites.sprites[a].velocity_x;
+                self.sprites.sprites[a].velocity_y = -self.sprites.sprites[a].velocity_y;
+                self.sprites.sprites[b].velocity_x = -self.sprites.sprites[b].velocity_x;
+                self.sprites.sprites[b].velocity_y = -self.sprites.sprites[b].velocity_y;
+            }
+        }
+        events
+    }
+
+    // Pure, deterministic advance of one fixed timestep. Given the same state
+    // and input it always produces the same next state — the property rollback
+    // replay depends on. The returned flags let the (non-deterministic) audio
+    // layer react without leaking side effects into the simulation.
+    fn step(state: &mut SimState, input: Input) -> StepFx {
+        if let Some((x, y)) = input.redirect {
+            state.ball.redirect_towards(x, y);
+        }
+        if let Some((ax, ay)) = input.accelerate {
+            state.ball.velocity_x += ax;
+            state.ball.velocity_y += ay;
+        }
+
+        let wall_bounce = state.ball.update(TIMESTEP, state.width, state.height);
+        let mut brick_hit = false;
+
+        // Reverse the ball off any intact brick whose AABB contains its center.
+        for brick in state.bricks.grid.iter_mut().flatten() {
+            if brick.status == 1
+                && state.ball.x > brick.x
+                && state.ball.x < brick.x + brick.width
+                && state.ball.y > brick.y
+                && state.ball.y < brick.y + brick.height
+            {
+                brick.status = 0;
+                state.ball.velocity_y = -state.ball.velocity_y;
+                state.score += 1;
+                brick_hit = true;
+            }
+        }
+
+        if !state.won && state.bricks.remaining() == 0 {
+            state.won = true;
+        }
+
+        StepFx { wall_bounce, brick_hit }
+    }
+
+    // Record the pre-step snapshot for `frame`, evicting the oldest entry once
+    // the ring buffer is full.
+    fn record(&mut self, frame: u64, input: Input) {
+        if self.history.len() >= HISTORY_LEN {
+            self.history.pop_front();
+        }
+        self.history.push_back((frame, self.sim.clone(), input));
+    }
+
+    // Restore the snapshot taken at `frame`, swap in the corrected input for
+    // that frame, and re-simulate every buffered frame up to the present. This
+    // is the correction path peer-to-peer netcode runs when a late input
+    // arrives for an already-simulated frame.
+    fn rollback_and_replay(&mut self, frame: u64, corrected: Input) {
+        GameState::rollback_and_replay_buffer(&mut self.history, &mut self.sim, frame, corrected);
+    }
+
+    // The DOM-free core of `rollback_and_replay`, operating directly on a ring
+    // buffer and simulation state so it can be exercised without a `GameState`.
+    // Restores the snapshot taken at `frame`, applies `corrected` in place of the
+    // original input for that frame, and re-simulates every buffered frame
+    // forward, re-recording each pre-step snapshot as it goes.
+    fn rollback_and_replay_buffer(
+        history: &mut std::collections::VecDeque<(u64, SimState, Input)>,
+        sim: &mut SimState,
+        frame: u64,
+        corrected: Input,
+    ) {
+        let Some(pos) = history.iter().position(|(f, _, _)| *f == frame) else {
+            return;
+        };
+
+        *sim = history[pos].1.clone();
+        let replay: Vec<(u64, Input)> = history
+            .iter()
+            .skip(pos)
+            .map(|(f, _, input)| (*f, input.clone()))
+            .collect();
+        history.truncate(pos);
+
+        for (i, (f, input)) in replay.into_iter().enumerate() {
+            let input = if i == 0 { corrected.clone() } else { input };
+            if history.len() >= HISTORY_LEN {
+                history.pop_front();
+            }
+            history.push_back((f, sim.clone(), input.clone()));
+            GameState::step(sim, input);
+        }
+    }
+
+    // Draw the score and a win banner over the playfield.
+    fn render_hud(&self) {
+        self.context.set_fill_style(&JsValue::from_str("#ffffff"));
+        self.context.set_font("16px sans-serif");
+        let _ = self.context.fill_text(&format!("Score: {}", self.sim.score), 8.0, 20.0);
+        if self.sim.won {
+            let _ = self.context.fill_text("YOU WIN!", self.canvas.width() as f64 / 2.0 - 40.0, self.canvas.height() as f64 / 2.0);
+        }
+    }
+
     // Main game loop - will become Bevy's Update schedule
     fn update(&mut self, current_time: f64) {
         let delta_time = if self.last_frame_time == 0.0 {
@@ -114,18 +623,78 @@ impl GameState {
         };
         self.last_frame_time = current_time;
 
-        // Update game logic
-        self.ball.update(delta_time, self.canvas.width() as f64, self.canvas.height() as f64);
+        // Accumulate real time and drain it in fixed steps so the simulation
+        // runs at a constant rate independent of the display refresh.
+        self.accumulator += delta_time;
+        while self.accumulator >= TIMESTEP {
+            let mut input = std::mem::take(&mut self.pending_input);
+            {
+                let mut keyboard = self.keyboard.borrow_mut();
+                let mut ax = 0.0;
+                let mut ay = 0.0;
+                // Continuous thrust: every arrow key still held this step nudges
+                // the ball again, so holding a key accelerates instead of moving
+                // the ball once.
+                for key in ["ArrowUp", "ArrowDown", "ArrowLeft", "ArrowRight"] {
+                    if keyboard.pressed(key) {
+                        if let Some((kx, ky)) = arrow_to_accel(key) {
+                            ax += kx;
+                            ay += ky;
+                        }
+                    }
+                }
+                // A tap that began and ended between steps leaves nothing held,
+                // but its buffered press still earns one nudge.
+                if let Some(key) = keyboard.take_press() {
+                    if !keyboard.pressed(&key) {
+                        if let Some((kx, ky)) = arrow_to_accel(&key) {
+                            ax += kx;
+                            ay += ky;
+                        }
+                    }
+                }
+                if ax != 0.0 || ay != 0.0 {
+                    input.accelerate = Some((ax, ay));
+                }
+            }
+            self.record(self.frame, input.clone());
+            let fx = GameState::step(&mut self.sim, input);
+            if let Some(audio) = &self.audio {
+                if fx.wall_bounce {
+                    audio.play("bounce");
+                }
+                if fx.brick_hit {
+                    audio.play("brick");
+                }
+            }
+            self.accumulator -= TIMESTEP;
+            self.frame += 1;
+        }
 
-        // Clear and render
+        // Advance the generic collision-event sprites alongside the ball.
+        let events = self.update_sprites();
+        if let Some(audio) = &self.audio {
+            if events.iter().any(|event| event.just_began) {
+                audio.play("collision");
+            }
+        }
+
+        // Rendering stays outside the fixed-step loop.
         self.context.clear_rect(0.0, 0.0, self.canvas.width().into(), self.canvas.height().into());
-        self.ball.render(&self.context);
+        self.sim.bricks.render(&self.context);
+        self.sim.ball.render(&self.context);
+        self.render_hud();
     }
 
     // Handle mouse input - will become Bevy input events
     fn handle_click(&mut self, x: f64, y: f64) {
-        self.ball.redirect_towards(x, y);
-        
+        // Resume audio on the first user gesture, then buffer the redirect for
+        // the next fixed step so input is applied deterministically inside `step`.
+        if let Some(audio) = &mut self.audio {
+            audio.resume();
+        }
+        self.pending_input.redirect = Some((x, y));
+
         // Log interaction for debugging
         web_sys::console::log_1(&format!("Click at ({:.0}, {:.0}), ball heading towards it", x, y).into());
     }
@@ -152,6 +721,15 @@ impl Game {
         Ok(game)
     }
 
+    // Apply a corrected redirect for an already-simulated frame and re-simulate
+    // forward. Exposed for a peer-to-peer layer that receives a late input for a
+    // frame the local sim has already run past.
+    #[wasm_bindgen]
+    pub fn rollback_redirect(&self, frame: u64, x: f64, y: f64) {
+        let corrected = Input { redirect: Some((x, y)), accelerate: None };
+        self.state.borrow_mut().rollback_and_replay(frame, corrected);
+    }
+
     #[wasm_bindgen]
     pub fn start(&self) -> Result<(), JsValue> {
         let state = self.state.clone();
@@ -170,6 +748,23 @@ impl Game {
         state.borrow().canvas.add_event_listener_with_callback("click", click_closure.as_ref().unchecked_ref())?;
         click_closure.forget();
 
+        // Set up keyboard handlers on the window so arrow keys drive the ball.
+        let keyboard = state.borrow().keyboard.clone();
+        let window = web_sys::window().unwrap();
+
+        let keydown_keyboard = keyboard.clone();
+        let keydown_closure = Closure::wrap(Box::new(move |event: web_sys::KeyboardEvent| {
+            keydown_keyboard.borrow_mut().press(event.key());
+        }) as Box<dyn FnMut(_)>);
+        window.add_event_listener_with_callback("keydown", keydown_closure.as_ref().unchecked_ref())?;
+        keydown_closure.forget();
+
+        let keyup_closure = Closure::wrap(Box::new(move |event: web_sys::KeyboardEvent| {
+            keyboard.borrow_mut().release(&event.key());
+        }) as Box<dyn FnMut(_)>);
+        window.add_event_listener_with_callback("keyup", keyup_closure.as_ref().unchecked_ref())?;
+        keyup_closure.forget();
+
         // Start game loop
         let loop_state = state.clone();
         let game_loop: Rc<RefCell<Option<Closure<dyn FnMut(f64)>>>> = Rc::new(RefCell::new(None));
@@ -194,4 +789,93 @@ impl Game {
 #[wasm_bindgen(start)]
 pub fn main() {
     console_error_panic_hook::set_once();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn approaching_sprites_report_no_collision() {
+        let mut set = SpriteSet::new();
+        // Two sprites 100px apart with size 20, closing on each other.
+        set.sprites.push(Sprite::new(100.0, 100.0, 50.0, 0.0, 20.0));
+        set.sprites.push(Sprite::new(200.0, 100.0, -50.0, 0.0, 20.0));
+        set.update(0.1, 800.0, 600.0);
+
+        // After one step they are 90px apart - still well clear of each other.
+        assert!(set.detect_collisions().is_empty());
+    }
+
+    #[test]
+    fn overlap_fires_a_single_just_began_event() {
+        let mut set = SpriteSet::new();
+        set.sprites.push(Sprite::new(0.0, 0.0, 0.0, 0.0, 20.0));
+        set.sprites.push(Sprite::new(100.0, 0.0, 0.0, 0.0, 20.0));
+        assert!(set.detect_collisions().is_empty());
+
+        // Move the second sprite on top of the first.
+        set.sprites[1].x = 10.0;
+        let events = set.detect_collisions();
+        assert_eq!(events, vec![CollisionEvent { a: 0, b: 1, just_began: true }]);
+
+        // Still overlapping next frame - no repeat event.
+        assert!(set.detect_collisions().is_empty());
+    }
+
+    #[test]
+    fn separation_fires_an_exit_event() {
+        let mut set = SpriteSet::new();
+        set.sprites.push(Sprite::new(0.0, 0.0, 0.0, 0.0, 20.0));
+        set.sprites.push(Sprite::new(10.0, 0.0, 0.0, 0.0, 20.0));
+
+        // Enter.
+        assert_eq!(
+            set.detect_collisions(),
+            vec![CollisionEvent { a: 0, b: 1, just_began: true }]
+        );
+
+        // Pull them apart - one exit event.
+        set.sprites[1].x = 100.0;
+        assert_eq!(
+            set.detect_collisions(),
+            vec![CollisionEvent { a: 0, b: 1, just_began: false }]
+        );
+
+        // Fully separated afterwards - quiet.
+        assert!(set.detect_collisions().is_empty());
+    }
+
+    #[test]
+    fn rollback_replay_matches_fresh_forward_sim() {
+        use std::collections::VecDeque;
+
+        // Record and simulate five frames with no-op input, as the game loop does.
+        let mut sim = SimState::new(800.0, 600.0);
+        let mut history: VecDeque<(u64, SimState, Input)> = VecDeque::new();
+        for frame in 0..5u64 {
+            history.push_back((frame, sim.clone(), Input::default()));
+            GameState::step(&mut sim, Input::default());
+        }
+
+        // A late corrected input arrives for frame 2; roll back and replay.
+        let corrected = Input { redirect: Some((120.0, 450.0)), accelerate: None };
+        GameState::rollback_and_replay_buffer(&mut history, &mut sim, 2, corrected.clone());
+
+        // A fresh forward simulation applying the correction at frame 2 must land
+        // in exactly the same state.
+        let mut expected = SimState::new(800.0, 600.0);
+        let inputs = [
+            Input::default(),
+            Input::default(),
+            corrected,
+            Input::default(),
+            Input::default(),
+        ];
+        for input in inputs {
+            GameState::step(&mut expected, input);
+        }
+
+        assert!(sim == expected);
+    }
 }
\ No newline at end of file